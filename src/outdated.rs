@@ -1,4 +1,8 @@
 //! This parses the output of dotnet-outdated
+use quick_xml::events::Event;
+use quick_xml::Reader;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
 use std::process::Command;
 use std::str::from_utf8;
 use tracing::{debug, trace, warn};
@@ -79,6 +83,12 @@ pub struct DotnetOutdatedOptions {
         help = "Include auto-referenced packages"
     )]
     include_auto_references: bool,
+    /// Include packages with a known vulnerability advisory
+    #[clap(
+        long = "include-vulnerable",
+        help = "Include packages with a known vulnerability advisory"
+    )]
+    include_vulnerable: bool,
     /// Should dotnet-outdated look for pre-release version of packages
     #[clap(
         long = "pre-release",
@@ -131,6 +141,15 @@ pub struct DotnetOutdatedOptions {
         arg_enum
     )]
     version_lock: VersionLock,
+    /// which dependencies to keep in the result, post-filtered by vulnerability status
+    #[clap(
+        long = "update-filter",
+        value_name = "FILTER",
+        default_value = "all",
+        help = "Which dependencies to keep in the result: all, only vulnerable ones, or only critically vulnerable ones",
+        arg_enum
+    )]
+    update_filter: UpdateFilter,
     /// path to pass to dotnet-outdated, defaults to current directory
     #[clap(
         long = "input-dir",
@@ -138,6 +157,26 @@ pub struct DotnetOutdatedOptions {
         help = "The input directory to pass to dotnet outdated"
     )]
     input_dir: Option<std::path::PathBuf>,
+    /// when upgrading, only compute and report what would change without touching any project files
+    #[clap(
+        long = "dry-run",
+        help = "When upgrading, only report the changes that would be made without touching any project files"
+    )]
+    dry_run: bool,
+    /// reuse a cached result from a previous call instead of always invoking dotnet outdated
+    #[clap(
+        long = "cache",
+        help = "Cache outdated results on disk, keyed by project file fingerprints, and reuse them until they expire"
+    )]
+    use_cache: bool,
+    /// how long a cached result remains valid
+    #[clap(
+        long = "cache-max-age-secs",
+        value_name = "SECONDS",
+        default_value = "3600",
+        help = "How long a cached outdated result remains valid, in seconds"
+    )]
+    cache_max_age_secs: u64,
 }
 
 /// Outer structure for parsing donet-outdated output
@@ -148,6 +187,142 @@ pub struct DotnetOutdatedData {
     pub projects: Vec<Project>,
 }
 
+impl DotnetOutdatedData {
+    /// Keep only the dependencies (and, transitively, the frameworks and
+    /// projects that still have any left) that match the given
+    /// [`UpdateFilter`]. Useful for reducing a full report down to just
+    /// the packages with outstanding vulnerability advisories before
+    /// wiring it into a CI gate.
+    pub fn filter(self, filter: &UpdateFilter) -> DotnetOutdatedData {
+        let projects = self
+            .projects
+            .into_iter()
+            .filter_map(|project| {
+                let target_frameworks: Vec<Framework> = project
+                    .target_frameworks
+                    .into_iter()
+                    .filter_map(|framework| {
+                        let dependencies: Vec<Dependency> = framework
+                            .dependencies
+                            .into_iter()
+                            .filter(|dependency| dependency.matches_filter(filter))
+                            .collect();
+                        if dependencies.is_empty() {
+                            None
+                        } else {
+                            Some(Framework {
+                                dependencies,
+                                ..framework
+                            })
+                        }
+                    })
+                    .collect();
+                if target_frameworks.is_empty() {
+                    None
+                } else {
+                    Some(Project {
+                        target_frameworks,
+                        ..project
+                    })
+                }
+            })
+            .collect();
+        DotnetOutdatedData { projects }
+    }
+
+    /// Parse every dependency's versions into semver, correct
+    /// `upgrade_severity` from the computed delta, and defensively drop any
+    /// dependency whose `latest_version` violates `version_lock`.
+    fn parse_semver_and_enforce_lock(&mut self, version_lock: &VersionLock) {
+        for project in &mut self.projects {
+            for framework in &mut project.target_frameworks {
+                framework.dependencies.retain_mut(|dependency| {
+                    dependency.parse_semver();
+                    match dependency.version_jump() {
+                        Some(jump) => {
+                            dependency.upgrade_severity = jump.severity.clone();
+                            respects_version_lock(&jump.severity, version_lock)
+                        }
+                        None => true,
+                    }
+                });
+            }
+        }
+    }
+
+    /// Summarize the outdated dependencies across every project and target
+    /// framework: total count, a breakdown by [`Severity`], the set of
+    /// distinct package names needing an upgrade, and the worst severity
+    /// present.
+    pub fn summary(&self) -> OutdatedSummary {
+        let mut summary = OutdatedSummary {
+            total_outdated: 0,
+            major_count: 0,
+            minor_count: 0,
+            patch_count: 0,
+            package_names: std::collections::BTreeSet::new(),
+            worst_severity: None,
+        };
+
+        for project in &self.projects {
+            for framework in &project.target_frameworks {
+                for dependency in &framework.dependencies {
+                    summary.total_outdated += 1;
+                    summary.package_names.insert(dependency.name.clone());
+                    match dependency.upgrade_severity {
+                        Severity::Major => summary.major_count += 1,
+                        Severity::Minor => summary.minor_count += 1,
+                        Severity::Patch => summary.patch_count += 1,
+                    }
+                    if summary
+                        .worst_severity
+                        .as_ref()
+                        .is_none_or(|worst| dependency.upgrade_severity > *worst)
+                    {
+                        summary.worst_severity = Some(dependency.upgrade_severity.clone());
+                    }
+                }
+            }
+        }
+
+        summary
+    }
+}
+
+/// Aggregate counts of outdated dependencies across every project and
+/// target framework, as returned by [`DotnetOutdatedData::summary`]
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct OutdatedSummary {
+    /// total number of outdated dependencies, counted once per project/
+    /// target framework combination that references them
+    pub total_outdated: usize,
+    /// number of dependencies with a major version upgrade pending
+    pub major_count: usize,
+    /// number of dependencies with a minor version upgrade pending
+    pub minor_count: usize,
+    /// number of dependencies with a patch level upgrade pending
+    pub patch_count: usize,
+    /// distinct package names that need an upgrade
+    pub package_names: std::collections::BTreeSet<String>,
+    /// the worst (highest) severity present, if any dependency is outdated
+    pub worst_severity: Option<Severity>,
+}
+
+impl std::fmt::Display for OutdatedSummary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Severity    Count")?;
+        writeln!(f, "Major       {}", self.major_count)?;
+        writeln!(f, "Minor       {}", self.minor_count)?;
+        writeln!(f, "Patch       {}", self.patch_count)?;
+        write!(
+            f,
+            "{} outdated across {} package(s)",
+            self.total_outdated,
+            self.package_names.len()
+        )
+    }
+}
+
 /// Per project data
 #[derive(Debug, serde::Serialize, serde::Deserialize)]
 #[serde(rename_all = "PascalCase")]
@@ -171,7 +346,7 @@ pub struct Framework {
 }
 
 /// Data about each outdated dependency
-#[derive(Debug, serde::Serialize, serde::Deserialize)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 #[serde(rename_all = "PascalCase")]
 pub struct Dependency {
     /// Name of the dependency
@@ -182,17 +357,161 @@ pub struct Dependency {
     pub latest_version: String,
     /// severity of this upgrade
     pub upgrade_severity: Severity,
+    /// known vulnerability advisories against this dependency, if any
+    #[serde(default)]
+    pub vulnerabilities: Option<Vec<Vulnerability>>,
+    /// `resolved_version` parsed as a semver version, if it is valid semver
+    #[serde(skip, default)]
+    pub resolved_semver: Option<semver::Version>,
+    /// `latest_version` parsed as a semver version, if it is valid semver
+    #[serde(skip, default)]
+    pub latest_semver: Option<semver::Version>,
+}
+
+impl Dependency {
+    /// does this dependency match the given [`UpdateFilter`]?
+    fn matches_filter(&self, filter: &UpdateFilter) -> bool {
+        match filter {
+            UpdateFilter::All => true,
+            UpdateFilter::VulnerableOnly => self
+                .vulnerabilities
+                .as_ref()
+                .is_some_and(|vulnerabilities| !vulnerabilities.is_empty()),
+            UpdateFilter::CriticalOnly => self.vulnerabilities.as_ref().is_some_and(|vulnerabilities| {
+                vulnerabilities
+                    .iter()
+                    .any(|vulnerability| vulnerability.severity == VulnerabilitySeverity::Critical)
+            }),
+        }
+    }
+
+    /// Parse `resolved_version` and `latest_version` into
+    /// [`semver::Version`], caching them on `resolved_semver`/
+    /// `latest_semver` so callers can sort dependencies by how far behind
+    /// they are without re-parsing.
+    fn parse_semver(&mut self) {
+        self.resolved_semver = semver::Version::parse(&self.resolved_version).ok();
+        self.latest_semver = semver::Version::parse(&self.latest_version).ok();
+    }
+
+    /// Classify the change from `resolved_version` to `latest_version`,
+    /// computed directly from semver rather than trusted verbatim from
+    /// the dotnet-outdated CLI output. Returns `None` if either version
+    /// could not be parsed as semver.
+    pub fn version_jump(&self) -> Option<VersionJump> {
+        let resolved = self.resolved_semver.as_ref()?;
+        let latest = self.latest_semver.as_ref()?;
+        let severity = if latest.major != resolved.major {
+            Severity::Major
+        } else if latest.minor != resolved.minor {
+            Severity::Minor
+        } else {
+            Severity::Patch
+        };
+        Some(VersionJump {
+            severity,
+            pre_release_transition: resolved.pre.is_empty() != latest.pre.is_empty(),
+        })
+    }
+}
+
+/// The kind of version jump between a dependency's `resolved_version` and
+/// `latest_version`, computed directly from semver by [`Dependency::version_jump`]
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct VersionJump {
+    /// Major/Minor/Patch classification of the change
+    pub severity: Severity,
+    /// whether this change moves between a pre-release and a full release
+    pub pre_release_transition: bool,
+}
+
+/// A known vulnerability advisory against a dependency
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct Vulnerability {
+    /// URL of the advisory describing this vulnerability
+    pub advisory_url: String,
+    /// severity of this vulnerability
+    pub severity: VulnerabilitySeverity,
+}
+
+/// Severity of a known vulnerability advisory
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, serde::Serialize, serde::Deserialize)]
+pub enum VulnerabilitySeverity {
+    /// a low severity advisory
+    Low,
+    /// a moderate severity advisory
+    Moderate,
+    /// a high severity advisory
+    High,
+    /// a critical severity advisory
+    Critical,
+}
+
+impl std::fmt::Display for VulnerabilitySeverity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            VulnerabilitySeverity::Low => {
+                write!(f, "Low")
+            }
+            VulnerabilitySeverity::Moderate => {
+                write!(f, "Moderate")
+            }
+            VulnerabilitySeverity::High => {
+                write!(f, "High")
+            }
+            VulnerabilitySeverity::Critical => {
+                write!(f, "Critical")
+            }
+        }
+    }
+}
+
+/// Which dependencies to keep when post-filtering a [`DotnetOutdatedData`]
+#[derive(Debug, Clone, clap::ArgEnum)]
+pub enum UpdateFilter {
+    /// keep every outdated dependency
+    All,
+    /// keep only dependencies with an outstanding vulnerability advisory
+    VulnerableOnly,
+    /// keep only dependencies with a critical vulnerability advisory
+    CriticalOnly,
+}
+
+impl Default for UpdateFilter {
+    fn default() -> Self {
+        UpdateFilter::All
+    }
+}
+
+impl std::fmt::Display for UpdateFilter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            UpdateFilter::All => {
+                write!(f, "All")
+            }
+            UpdateFilter::VulnerableOnly => {
+                write!(f, "VulnerableOnly")
+            }
+            UpdateFilter::CriticalOnly => {
+                write!(f, "CriticalOnly")
+            }
+        }
+    }
 }
 
 /// Severity of a required upgrade
-#[derive(Debug, serde::Serialize, serde::Deserialize)]
+///
+/// Variants are declared from least to most severe so that the derived
+/// `Ord` can be used directly to find the worst severity present.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, serde::Serialize, serde::Deserialize)]
 pub enum Severity {
-    /// a major version upgrade
-    Major,
-    /// a minor version uprade
-    Minor,
     /// a patch level upgrade
     Patch,
+    /// a minor version uprade
+    Minor,
+    /// a major version upgrade
+    Major,
 }
 
 impl std::fmt::Display for Severity {
@@ -234,30 +553,42 @@ impl std::fmt::Display for IndicatedUpdateRequirement {
 }
 
 /// main entry point for the dotnet-oudated call
+///
+/// When `options.use_cache` is set, first looks for a fresh, fingerprint-matching
+/// cached result (see [`clear_cache`]) and returns it instead of invoking
+/// `dotnet outdated` again.
 pub fn outdated(
     options: &DotnetOutdatedOptions,
 ) -> Result<(IndicatedUpdateRequirement, DotnetOutdatedData), crate::Error> {
-    let output_dir = tempfile::tempdir()?;
-    let output_file = output_dir.path().join("outdated.json");
-    let output_file = output_file
-        .to_str()
-        .ok_or(crate::Error::PathConversionError)?;
+    if !options.use_cache {
+        return run_outdated(options);
+    }
 
-    let mut cmd = Command::new("dotnet");
+    let fingerprint = cache_fingerprint(options)?;
+    if let Some(mut cached) = read_cache_entry(&fingerprint, options.cache_max_age_secs)? {
+        debug!("returning cached dotnet outdated result for fingerprint {fingerprint}");
+        // `resolved_semver`/`latest_semver` are not part of the cached JSON
+        // (they are cheaply recomputed), so they must be reparsed here too.
+        cached.1.parse_semver_and_enforce_lock(&options.version_lock);
+        return Ok(cached);
+    }
 
-    cmd.args([
-        "outdated",
-        "--fail-on-updates",
-        "--output",
-        output_file,
-        "--output-format",
-        "json",
-    ]);
+    let result = run_outdated(options)?;
+    write_cache_entry(&fingerprint, &result.0, &result.1)?;
+    Ok(result)
+}
 
+/// Apply every `dotnet-outdated`-affecting flag in `options` to `cmd`,
+/// shared between [`run_outdated`] and [`upgrade`].
+fn apply_common_args(cmd: &mut Command, options: &DotnetOutdatedOptions) {
     if options.include_auto_references {
         cmd.args(["--include-auto-references"]);
     }
 
+    if options.include_vulnerable {
+        cmd.args(["--include-vulnerable"]);
+    }
+
     cmd.args(["--pre-release", &options.pre_release.to_string()]);
 
     if !options.include.is_empty() {
@@ -285,6 +616,30 @@ pub fn outdated(
     if let Some(ref input_dir) = options.input_dir {
         cmd.args([&input_dir]);
     }
+}
+
+/// Actually invoke `dotnet outdated` and parse its output, bypassing the cache
+fn run_outdated(
+    options: &DotnetOutdatedOptions,
+) -> Result<(IndicatedUpdateRequirement, DotnetOutdatedData), crate::Error> {
+    let output_dir = tempfile::tempdir()?;
+    let output_file = output_dir.path().join("outdated.json");
+    let output_file = output_file
+        .to_str()
+        .ok_or(crate::Error::PathConversionError)?;
+
+    let mut cmd = Command::new("dotnet");
+
+    cmd.args([
+        "outdated",
+        "--fail-on-updates",
+        "--output",
+        output_file,
+        "--output-format",
+        "json",
+    ]);
+
+    apply_common_args(&mut cmd, options);
 
     let output = cmd.output()?;
 
@@ -310,15 +665,547 @@ pub fn outdated(
     trace!("Read output file content:\n{}", output_file_content);
 
     let jd = &mut serde_json::Deserializer::from_str(&output_file_content);
-    let data: DotnetOutdatedData = serde_path_to_error::deserialize(jd)?;
+    let mut data: DotnetOutdatedData = serde_path_to_error::deserialize(jd)?;
+    data.parse_semver_and_enforce_lock(&options.version_lock);
+    let data = data.filter(&options.update_filter);
     Ok((update_requirement, data))
 }
 
+/// One dependency change that was (or, in a dry run, would be) applied by [`upgrade`]
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct DependencyChange {
+    /// Name of the dependency that changed
+    pub name: String,
+    /// the version it was pinned to before the upgrade
+    pub from_version: String,
+    /// the version it was (or would be) upgraded to
+    pub to_version: String,
+}
+
+/// Per project collection of the dependency changes that were (or, in a
+/// dry run, would be) applied by [`upgrade`]
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct ProjectUpgrade {
+    /// absolute path to the .csproj file that was (or would be) touched
+    pub file_path: String,
+    /// the dependency changes for this project
+    pub changes: Vec<DependencyChange>,
+}
+
+/// Does this severity stay within the bounds of the given version lock?
+fn respects_version_lock(severity: &Severity, version_lock: &VersionLock) -> bool {
+    match (version_lock, severity) {
+        (VersionLock::Major, Severity::Major) => false,
+        (VersionLock::Minor, Severity::Major) | (VersionLock::Minor, Severity::Minor) => false,
+        _ => true,
+    }
+}
+
+/// Collect the per-project dependency changes that `outdated` found,
+/// dropping any that the requested `VersionLock` should have excluded
+/// already (defensively, in case dotnet-outdated itself did not honor it).
+fn pending_upgrades(options: &DotnetOutdatedOptions, data: &DotnetOutdatedData) -> Vec<ProjectUpgrade> {
+    let mut project_upgrades = Vec::new();
+    for project in &data.projects {
+        let mut changes = Vec::new();
+        for framework in &project.target_frameworks {
+            for dependency in &framework.dependencies {
+                if !respects_version_lock(&dependency.upgrade_severity, &options.version_lock) {
+                    continue;
+                }
+                changes.push(DependencyChange {
+                    name: dependency.name.clone(),
+                    from_version: dependency.resolved_version.clone(),
+                    to_version: dependency.latest_version.clone(),
+                });
+            }
+        }
+        if !changes.is_empty() {
+            project_upgrades.push(ProjectUpgrade {
+                file_path: project.file_path.clone(),
+                changes,
+            });
+        }
+    }
+    project_upgrades
+}
+
+/// Rewrite a single `<PackageReference>` element's `Version` attribute if
+/// its `Include` names a package in `to_versions`, regardless of attribute
+/// order. Returns the element and, when a rewrite was applied, the name.
+fn rewrite_package_reference(
+    e: &quick_xml::events::BytesStart,
+    to_versions: &HashMap<String, String>,
+) -> Result<(quick_xml::events::BytesStart<'static>, Option<String>), crate::Error> {
+    let mut name = None;
+    let mut attributes = Vec::new();
+    for attribute in e.attributes().filter_map(Result::ok) {
+        let key = String::from_utf8_lossy(attribute.key.as_ref()).into_owned();
+        let value = attribute.unescape_value()?.into_owned();
+        if key == "Include" {
+            name = Some(value.clone());
+        }
+        attributes.push((key, value));
+    }
+
+    let mut applied = None;
+    if let Some(to_version) = name.as_ref().and_then(|name| to_versions.get(name)) {
+        if let Some(version_attribute) = attributes.iter_mut().find(|(key, _)| key == "Version") {
+            version_attribute.1 = to_version.clone();
+            applied = name.clone();
+        }
+    }
+
+    let mut new_element =
+        quick_xml::events::BytesStart::new(String::from_utf8_lossy(e.name().as_ref()).into_owned());
+    for (key, value) in &attributes {
+        new_element.push_attribute((key.as_str(), value.as_str()));
+    }
+    Ok((new_element, applied))
+}
+
+/// Rewrite a project file's `<PackageReference>` entries to apply the given
+/// changes. Returns the names of any changes that could not be applied
+/// (e.g. a package under central package management, with no `Version`
+/// attribute to rewrite).
+fn apply_project_upgrade(project_upgrade: &ProjectUpgrade) -> Result<Vec<String>, crate::Error> {
+    let to_versions: HashMap<String, String> = project_upgrade
+        .changes
+        .iter()
+        .map(|change| (change.name.clone(), change.to_version.clone()))
+        .collect();
+
+    let content = std::fs::read_to_string(&project_upgrade.file_path)?;
+    let mut reader = Reader::from_str(&content);
+    let mut writer = quick_xml::Writer::new(Vec::new());
+
+    let mut applied = std::collections::HashSet::new();
+    let mut buf = Vec::new();
+    loop {
+        let event = reader.read_event_into(&mut buf)?;
+        let is_eof = matches!(event, Event::Eof);
+        match event {
+            Event::Start(ref e) if e.name().as_ref() == b"PackageReference" => {
+                let (new_element, name) = rewrite_package_reference(e, &to_versions)?;
+                if let Some(name) = name {
+                    applied.insert(name);
+                    writer.write_event(Event::Start(new_element))?;
+                } else {
+                    writer.write_event(Event::Start(e.clone()))?;
+                }
+            }
+            Event::Empty(ref e) if e.name().as_ref() == b"PackageReference" => {
+                let (new_element, name) = rewrite_package_reference(e, &to_versions)?;
+                if let Some(name) = name {
+                    applied.insert(name);
+                    writer.write_event(Event::Empty(new_element))?;
+                } else {
+                    writer.write_event(Event::Empty(e.clone()))?;
+                }
+            }
+            other => {
+                writer.write_event(other)?;
+            }
+        }
+        buf.clear();
+        if is_eof {
+            break;
+        }
+    }
+
+    let written = writer.into_inner();
+    let updated = from_utf8(&written)?;
+    if updated != content {
+        std::fs::write(&project_upgrade.file_path, updated)?;
+    }
+
+    Ok(project_upgrade
+        .changes
+        .iter()
+        .filter(|change| !applied.contains(&change.name))
+        .map(|change| change.name.clone())
+        .collect())
+}
+
+/// Rewrite every project's `<PackageReference>` entries directly, logging
+/// (rather than dropping) any change [`apply_project_upgrade`] could not apply.
+fn apply_project_upgrades(project_upgrades: &[ProjectUpgrade]) -> Result<(), crate::Error> {
+    for project_upgrade in project_upgrades {
+        let unapplied = apply_project_upgrade(project_upgrade)?;
+        for name in unapplied {
+            warn!(
+                "could not rewrite {} to its new version in {} (no matching Version attribute found, possibly central package management)",
+                name, project_upgrade.file_path
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Actually apply the pending updates that [`outdated`] would report.
+///
+/// Honors `options.version_lock` (a `Major` lock never bumps past the
+/// current major version): the lock is passed on to `dotnet outdated
+/// --upgrade`, and the returned changes and the direct rewrite below
+/// leave out any dependency that violates it, even if dotnet-outdated
+/// itself reported one. Also honors `options.dry_run`: when dry-running,
+/// the pending changes are computed and returned but no project file is
+/// touched.
+///
+/// Prefers shelling out to `dotnet outdated --upgrade`; if the `dotnet`
+/// binary cannot be found or exits unsuccessfully, falls back to
+/// rewriting the `Version` attribute of each affected `<PackageReference>`
+/// directly in the project's `file_path`. Changes that the direct rewrite
+/// cannot apply are logged and still listed in the returned result.
+pub fn upgrade(
+    options: &DotnetOutdatedOptions,
+) -> Result<(IndicatedUpdateRequirement, Vec<ProjectUpgrade>), crate::Error> {
+    let (update_requirement, data) = outdated(options)?;
+    let project_upgrades = pending_upgrades(options, &data);
+
+    if options.dry_run {
+        return Ok((update_requirement, project_upgrades));
+    }
+
+    let mut cmd = Command::new("dotnet");
+    cmd.args(["outdated", "--upgrade"]);
+    apply_common_args(&mut cmd, options);
+
+    match cmd.output() {
+        Ok(output) if output.status.success() => {
+            debug!("dotnet outdated --upgrade applied the pending updates");
+        }
+        Ok(output) => {
+            warn!(
+                "dotnet outdated --upgrade did not return with a successful exit code: {}, falling back to rewriting project files directly",
+                output.status
+            );
+            debug!("stdout:\n{}", from_utf8(&output.stdout)?);
+            if !output.stderr.is_empty() {
+                warn!("stderr:\n{}", from_utf8(&output.stderr)?);
+            }
+            apply_project_upgrades(&project_upgrades)?;
+        }
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+            debug!("dotnet binary not found, rewriting project files directly instead");
+            apply_project_upgrades(&project_upgrades)?;
+        }
+        Err(err) => return Err(err.into()),
+    }
+
+    Ok((update_requirement, project_upgrades))
+}
+
+/// Recursively collect every file under `root_dir` whose extension matches
+/// `extension`.
+fn find_files_with_extension(
+    root_dir: &std::path::Path,
+    extension: &str,
+) -> Result<Vec<std::path::PathBuf>, crate::Error> {
+    let mut matches = Vec::new();
+    let mut directories = vec![root_dir.to_path_buf()];
+    while let Some(directory) = directories.pop() {
+        for entry in std::fs::read_dir(&directory)? {
+            let path = entry?.path();
+            if path.is_dir() {
+                directories.push(path);
+            } else if path.extension().and_then(std::ffi::OsStr::to_str) == Some(extension) {
+                matches.push(path);
+            }
+        }
+    }
+    Ok(matches)
+}
+
+/// Read the central package versions declared by a `Directory.Packages.props`
+/// file directly under `root_dir`, if one is present. Returns an empty map
+/// when the project does not use central package management.
+fn read_central_package_versions(root_dir: &std::path::Path) -> Result<HashMap<String, String>, crate::Error> {
+    let props_path = root_dir.join("Directory.Packages.props");
+    if !props_path.is_file() {
+        return Ok(HashMap::new());
+    }
+
+    let content = std::fs::read_to_string(&props_path)?;
+    let mut reader = Reader::from_str(&content);
+    reader.trim_text(true);
+
+    let mut versions = HashMap::new();
+    let mut buf = Vec::new();
+    loop {
+        match reader.read_event_into(&mut buf)? {
+            Event::Start(ref e) | Event::Empty(ref e) if e.name().as_ref() == b"PackageVersion" => {
+                let mut name = None;
+                let mut version = None;
+                for attribute in e.attributes().filter_map(Result::ok) {
+                    match attribute.key.as_ref() {
+                        b"Include" => name = Some(attribute.unescape_value()?.into_owned()),
+                        b"Version" => version = Some(attribute.unescape_value()?.into_owned()),
+                        _ => {}
+                    }
+                }
+                if let (Some(name), Some(version)) = (name, version) {
+                    versions.insert(name, version);
+                }
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+        buf.clear();
+    }
+    Ok(versions)
+}
+
+/// Parse a single `.csproj` file's `<PackageReference>` entries and
+/// `<TargetFramework>`/`<TargetFrameworks>` with a streaming XML reader,
+/// falling back to `central_versions` for any package reference that
+/// omits a `Version` attribute (central package management).
+fn parse_csproj(path: &std::path::Path, central_versions: &HashMap<String, String>) -> Result<Project, crate::Error> {
+    let content = std::fs::read_to_string(path)?;
+    let mut reader = Reader::from_str(&content);
+    reader.trim_text(true);
+
+    let mut dependencies = Vec::new();
+    let mut framework_monikers = Vec::new();
+    let mut in_target_framework_element = false;
+    let mut buf = Vec::new();
+    loop {
+        match reader.read_event_into(&mut buf)? {
+            Event::Start(ref e)
+                if e.name().as_ref() == b"TargetFramework" || e.name().as_ref() == b"TargetFrameworks" =>
+            {
+                in_target_framework_element = true;
+            }
+            Event::End(ref e)
+                if e.name().as_ref() == b"TargetFramework" || e.name().as_ref() == b"TargetFrameworks" =>
+            {
+                in_target_framework_element = false;
+            }
+            Event::Text(ref e) if in_target_framework_element => {
+                let text = e.unescape()?.into_owned();
+                framework_monikers.extend(text.split(';').map(str::to_owned));
+            }
+            Event::Start(ref e) | Event::Empty(ref e) if e.name().as_ref() == b"PackageReference" => {
+                let mut name = None;
+                let mut version = None;
+                for attribute in e.attributes().filter_map(Result::ok) {
+                    match attribute.key.as_ref() {
+                        b"Include" => name = Some(attribute.unescape_value()?.into_owned()),
+                        b"Version" => version = Some(attribute.unescape_value()?.into_owned()),
+                        _ => {}
+                    }
+                }
+                if let Some(name) = name {
+                    let resolved_version = version
+                        .or_else(|| central_versions.get(&name).cloned())
+                        .unwrap_or_default();
+                    dependencies.push(Dependency {
+                        name,
+                        resolved_version,
+                        latest_version: String::new(),
+                        upgrade_severity: Severity::Patch,
+                        vulnerabilities: None,
+                        resolved_semver: None,
+                        latest_semver: None,
+                    });
+                }
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+        buf.clear();
+    }
+
+    if framework_monikers.is_empty() {
+        framework_monikers.push(String::new());
+    }
+
+    let target_frameworks = framework_monikers
+        .into_iter()
+        .map(|name| Framework {
+            name,
+            dependencies: dependencies.clone(),
+        })
+        .collect();
+
+    Ok(Project {
+        name: path
+            .file_stem()
+            .and_then(std::ffi::OsStr::to_str)
+            .unwrap_or_default()
+            .to_owned(),
+        file_path: path.to_string_lossy().into_owned(),
+        target_frameworks,
+    })
+}
+
+/// Pure-Rust fallback for environments where the `dotnet` CLI (and its
+/// `outdated` tool) are not installed, e.g. minimal CI images. Walks
+/// `root_dir` for `*.csproj` files and parses each one's
+/// `<PackageReference>` entries and target framework(s) directly,
+/// populating the same [`Project`]/[`Framework`]/[`Dependency`]
+/// structures that [`outdated`] returns. Package references that omit a
+/// `Version` attribute are resolved against `root_dir`'s central package
+/// management file, `Directory.Packages.props`, if present.
+///
+/// Only `resolved_version` is populated for each dependency;
+/// `latest_version` is left empty and `upgrade_severity` is left at
+/// `Severity::Patch` since no upgrade information is available without
+/// querying NuGet. This gives callers a deterministic, offline inventory
+/// of the current dependency graph.
+pub fn native_inventory(root_dir: &std::path::Path) -> Result<DotnetOutdatedData, crate::Error> {
+    let central_versions = read_central_package_versions(root_dir)?;
+
+    let projects = find_files_with_extension(root_dir, "csproj")?
+        .iter()
+        .map(|path| parse_csproj(path, &central_versions))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(DotnetOutdatedData { projects })
+}
+
+/// On-disk, deserialized shape of a cached [`outdated`] result
+#[derive(serde::Deserialize)]
+struct CacheEntry {
+    /// when this entry was written
+    cached_at: std::time::SystemTime,
+    /// the cached update requirement
+    update_requirement: IndicatedUpdateRequirement,
+    /// the cached outdated data
+    data: DotnetOutdatedData,
+}
+
+/// Borrowed counterpart of [`CacheEntry`] used when writing, so callers don't
+/// have to clone the result just to cache it
+#[derive(serde::Serialize)]
+struct CacheEntryRef<'a> {
+    /// when this entry is being written
+    cached_at: std::time::SystemTime,
+    /// the update requirement to cache
+    update_requirement: &'a IndicatedUpdateRequirement,
+    /// the outdated data to cache
+    data: &'a DotnetOutdatedData,
+}
+
+/// Directory under the platform cache dir that [`outdated`]'s cache lives
+/// in. Overridable via `DOTNET_PARSER_CACHE_DIR` so tests (and callers who
+/// want an isolated cache) don't have to touch the real platform cache dir.
+fn cache_dir() -> Result<std::path::PathBuf, crate::Error> {
+    if let Some(dir) = std::env::var_os("DOTNET_PARSER_CACHE_DIR") {
+        return Ok(std::path::PathBuf::from(dir));
+    }
+    let base = dirs::cache_dir().ok_or(crate::Error::CacheDirUnavailable)?;
+    Ok(base.join("dotnet-parser"))
+}
+
+/// Path of the cache file for a given fingerprint
+fn cache_entry_path(fingerprint: &str) -> Result<std::path::PathBuf, crate::Error> {
+    Ok(cache_dir()?.join(format!("{fingerprint}.json")))
+}
+
+/// Fingerprint the manifest files under `options.input_dir` (path and
+/// mtime) together with the effective options, so that any change to
+/// either invalidates a cached result. Besides every `*.csproj`, this also
+/// covers `Directory.Packages.props` and `global.json`, since `dotnet
+/// outdated` itself resolves package versions from them too.
+fn cache_fingerprint(options: &DotnetOutdatedOptions) -> Result<String, crate::Error> {
+    let root_dir = options
+        .input_dir
+        .clone()
+        .unwrap_or_else(|| std::path::PathBuf::from("."));
+    let mut manifests = find_files_with_extension(&root_dir, "csproj")?;
+    manifests.push(root_dir.join("Directory.Packages.props"));
+    manifests.push(root_dir.join("global.json"));
+    manifests.sort();
+
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    format!("{options:?}").hash(&mut hasher);
+    for manifest in &manifests {
+        manifest.hash(&mut hasher);
+        if let Ok(modified) = std::fs::metadata(manifest).and_then(|metadata| metadata.modified()) {
+            modified.hash(&mut hasher);
+        }
+    }
+    Ok(format!("{:x}", hasher.finish()))
+}
+
+/// Read a cached result for `fingerprint`, if one exists and is no older than
+/// `max_age_secs`.
+fn read_cache_entry(
+    fingerprint: &str,
+    max_age_secs: u64,
+) -> Result<Option<(IndicatedUpdateRequirement, DotnetOutdatedData)>, crate::Error> {
+    let path = cache_entry_path(fingerprint)?;
+    if !path.is_file() {
+        return Ok(None);
+    }
+
+    let content = std::fs::read_to_string(&path)?;
+    let entry: CacheEntry = serde_json::from_str(&content)?;
+    let age = std::time::SystemTime::now()
+        .duration_since(entry.cached_at)
+        .unwrap_or(std::time::Duration::MAX);
+    if age > std::time::Duration::from_secs(max_age_secs) {
+        return Ok(None);
+    }
+
+    Ok(Some((entry.update_requirement, entry.data)))
+}
+
+/// Write `update_requirement`/`data` to the cache under `fingerprint`.
+fn write_cache_entry(
+    fingerprint: &str,
+    update_requirement: &IndicatedUpdateRequirement,
+    data: &DotnetOutdatedData,
+) -> Result<(), crate::Error> {
+    let path = cache_entry_path(fingerprint)?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+
+    let entry = CacheEntryRef {
+        cached_at: std::time::SystemTime::now(),
+        update_requirement,
+        data,
+    };
+    std::fs::write(&path, serde_json::to_string(&entry)?)?;
+    Ok(())
+}
+
+/// Remove every cached [`outdated`] result from disk.
+pub fn clear_cache() -> Result<(), crate::Error> {
+    let dir = cache_dir()?;
+    if dir.is_dir() {
+        std::fs::remove_dir_all(&dir)?;
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
     use crate::Error;
 
+    /// Serializes tests that point `DOTNET_PARSER_CACHE_DIR` at a temp dir,
+    /// since it is process-wide state, and resets it on drop.
+    static CACHE_DIR_ENV_LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+
+    struct CacheDirEnvGuard(std::sync::MutexGuard<'static, ()>);
+
+    impl CacheDirEnvGuard {
+        fn set(dir: &std::path::Path) -> CacheDirEnvGuard {
+            let guard = CACHE_DIR_ENV_LOCK.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+            std::env::set_var("DOTNET_PARSER_CACHE_DIR", dir);
+            CacheDirEnvGuard(guard)
+        }
+    }
+
+    impl Drop for CacheDirEnvGuard {
+        fn drop(&mut self) {
+            std::env::remove_var("DOTNET_PARSER_CACHE_DIR");
+        }
+    }
+
     /// this test requires a .sln and/or .csproj files in the current
     /// directory (working dir of the tests)
     #[test]
@@ -326,4 +1213,369 @@ mod test {
         outdated(&Default::default())?;
         Ok(())
     }
+
+    /// build a minimal [`Dependency`] for tests, optionally with a vulnerability
+    fn test_dependency(name: &str, vulnerability_severity: Option<VulnerabilitySeverity>) -> Dependency {
+        Dependency {
+            name: name.to_owned(),
+            resolved_version: "1.0.0".to_owned(),
+            latest_version: "1.0.1".to_owned(),
+            upgrade_severity: Severity::Patch,
+            vulnerabilities: vulnerability_severity.map(|severity| {
+                vec![Vulnerability {
+                    advisory_url: "https://example.test/advisory".to_owned(),
+                    severity,
+                }]
+            }),
+            resolved_semver: None,
+            latest_semver: None,
+        }
+    }
+
+    fn test_data(dependencies: Vec<Dependency>) -> DotnetOutdatedData {
+        DotnetOutdatedData {
+            projects: vec![Project {
+                name: "Example".to_owned(),
+                file_path: "Example.csproj".to_owned(),
+                target_frameworks: vec![Framework {
+                    name: "net6.0".to_owned(),
+                    dependencies,
+                }],
+            }],
+        }
+    }
+
+    #[test]
+    fn test_update_filter_vulnerable_only() {
+        let data = test_data(vec![
+            test_dependency("Safe.Package", None),
+            test_dependency("Vulnerable.Package", Some(VulnerabilitySeverity::Moderate)),
+        ]);
+
+        let filtered = data.filter(&UpdateFilter::VulnerableOnly);
+        assert_eq!(filtered.projects.len(), 1);
+        let dependencies = &filtered.projects[0].target_frameworks[0].dependencies;
+        assert_eq!(dependencies.len(), 1);
+        assert_eq!(dependencies[0].name, "Vulnerable.Package");
+    }
+
+    #[test]
+    fn test_update_filter_critical_only() {
+        let data = test_data(vec![
+            test_dependency("Moderately.Vulnerable", Some(VulnerabilitySeverity::Moderate)),
+            test_dependency("Critically.Vulnerable", Some(VulnerabilitySeverity::Critical)),
+        ]);
+
+        let filtered = data.filter(&UpdateFilter::CriticalOnly);
+        let dependencies = &filtered.projects[0].target_frameworks[0].dependencies;
+        assert_eq!(dependencies.len(), 1);
+        assert_eq!(dependencies[0].name, "Critically.Vulnerable");
+    }
+
+    #[test]
+    fn test_update_filter_all_keeps_everything() {
+        let data = test_data(vec![test_dependency("Safe.Package", None)]);
+        let filtered = data.filter(&UpdateFilter::All);
+        assert_eq!(filtered.projects[0].target_frameworks[0].dependencies.len(), 1);
+    }
+
+    #[test]
+    fn test_summary() {
+        let mut major = test_dependency("Major.Package", None);
+        major.upgrade_severity = Severity::Major;
+        let mut minor = test_dependency("Minor.Package", None);
+        minor.upgrade_severity = Severity::Minor;
+        let mut patch = test_dependency("Patch.Package", None);
+        patch.upgrade_severity = Severity::Patch;
+
+        let summary = test_data(vec![major, minor, patch]).summary();
+        assert_eq!(summary.total_outdated, 3);
+        assert_eq!(summary.major_count, 1);
+        assert_eq!(summary.minor_count, 1);
+        assert_eq!(summary.patch_count, 1);
+        assert_eq!(summary.package_names.len(), 3);
+        assert_eq!(summary.worst_severity, Some(Severity::Major));
+
+        assert!(summary.to_string().contains("3 outdated across 3 package(s)"));
+    }
+
+    #[test]
+    fn test_version_jump_major() {
+        let mut dependency = test_dependency("Some.Package", None);
+        dependency.resolved_version = "1.2.3".to_owned();
+        dependency.latest_version = "2.0.0".to_owned();
+        dependency.parse_semver();
+        let jump = dependency.version_jump().expect("both versions are valid semver");
+        assert_eq!(jump.severity, Severity::Major);
+        assert!(!jump.pre_release_transition);
+    }
+
+    #[test]
+    fn test_version_jump_pre_release_transition() {
+        let mut dependency = test_dependency("Some.Package", None);
+        dependency.resolved_version = "1.2.3-beta.1".to_owned();
+        dependency.latest_version = "1.2.3".to_owned();
+        dependency.parse_semver();
+        let jump = dependency.version_jump().expect("both versions are valid semver");
+        assert_eq!(jump.severity, Severity::Patch);
+        assert!(jump.pre_release_transition);
+    }
+
+    #[test]
+    fn test_version_jump_unparseable_returns_none() {
+        let mut dependency = test_dependency("Some.Package", None);
+        dependency.resolved_version = "not-semver".to_owned();
+        dependency.latest_version = "also-not-semver".to_owned();
+        dependency.parse_semver();
+        assert!(dependency.version_jump().is_none());
+    }
+
+    #[test]
+    fn test_respects_version_lock() {
+        assert!(respects_version_lock(&Severity::Patch, &VersionLock::Major));
+        assert!(!respects_version_lock(&Severity::Major, &VersionLock::Major));
+        assert!(!respects_version_lock(&Severity::Minor, &VersionLock::Minor));
+        assert!(respects_version_lock(&Severity::Patch, &VersionLock::Minor));
+        assert!(respects_version_lock(&Severity::Major, &VersionLock::None));
+    }
+
+    #[test]
+    fn test_apply_project_upgrade_rewrites_version_attribute() -> Result<(), Error> {
+        let dir = tempfile::tempdir()?;
+        let project_path = dir.path().join("Example.csproj");
+        std::fs::write(
+            &project_path,
+            r#"<Project Sdk="Microsoft.NET.Sdk">
+  <ItemGroup>
+    <PackageReference Version="1.0.0" Include="Newtonsoft.Json" />
+  </ItemGroup>
+</Project>
+"#,
+        )?;
+
+        let project_upgrade = ProjectUpgrade {
+            file_path: project_path.to_string_lossy().into_owned(),
+            changes: vec![DependencyChange {
+                name: "Newtonsoft.Json".to_owned(),
+                from_version: "1.0.0".to_owned(),
+                to_version: "13.0.1".to_owned(),
+            }],
+        };
+
+        let unapplied = apply_project_upgrade(&project_upgrade)?;
+        assert!(unapplied.is_empty());
+
+        let rewritten = std::fs::read_to_string(&project_path)?;
+        assert!(rewritten.contains(r#"Version="13.0.1""#));
+        assert!(!rewritten.contains(r#"Version="1.0.0""#));
+        Ok(())
+    }
+
+    #[test]
+    fn test_apply_project_upgrade_reports_central_package_management_as_unapplied() -> Result<(), Error> {
+        let dir = tempfile::tempdir()?;
+        let project_path = dir.path().join("Example.csproj");
+        let original = r#"<Project Sdk="Microsoft.NET.Sdk">
+  <ItemGroup>
+    <PackageReference Include="Centrally.Pinned.Package" />
+  </ItemGroup>
+</Project>
+"#;
+        std::fs::write(&project_path, original)?;
+
+        let project_upgrade = ProjectUpgrade {
+            file_path: project_path.to_string_lossy().into_owned(),
+            changes: vec![DependencyChange {
+                name: "Centrally.Pinned.Package".to_owned(),
+                from_version: "1.0.0".to_owned(),
+                to_version: "2.0.0".to_owned(),
+            }],
+        };
+
+        let unapplied = apply_project_upgrade(&project_upgrade)?;
+        assert_eq!(unapplied, vec!["Centrally.Pinned.Package".to_owned()]);
+        assert_eq!(std::fs::read_to_string(&project_path)?, original);
+        Ok(())
+    }
+
+    #[test]
+    fn test_cache_round_trip_reparses_semver_fields() -> Result<(), Error> {
+        let mut dependency = test_dependency("Some.Package", None);
+        dependency.resolved_version = "1.0.0".to_owned();
+        dependency.latest_version = "2.0.0".to_owned();
+        dependency.parse_semver();
+        assert!(dependency.resolved_semver.is_some());
+
+        let data = test_data(vec![dependency]);
+        let serialized = serde_json::to_string(&CacheEntryRef {
+            cached_at: std::time::SystemTime::now(),
+            update_requirement: &IndicatedUpdateRequirement::UpdateRequired,
+            data: &data,
+        })?;
+
+        // `resolved_semver`/`latest_semver` are `#[serde(skip)]` and so do
+        // not survive the JSON round trip on their own...
+        let mut entry: CacheEntry = serde_json::from_str(&serialized)?;
+        assert!(entry.data.projects[0].target_frameworks[0].dependencies[0]
+            .resolved_semver
+            .is_none());
+
+        // ...which is why `outdated()` must reparse them before returning a
+        // cache hit.
+        entry.data.parse_semver_and_enforce_lock(&VersionLock::None);
+        assert!(entry.data.projects[0].target_frameworks[0].dependencies[0]
+            .resolved_semver
+            .is_some());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_cache_entry_fresh_fingerprint_hit() -> Result<(), Error> {
+        let dir = tempfile::tempdir()?;
+        let _guard = CacheDirEnvGuard::set(dir.path());
+
+        let data = test_data(vec![test_dependency("Some.Package", None)]);
+        write_cache_entry("some-fingerprint", &IndicatedUpdateRequirement::UpdateRequired, &data)?;
+
+        let cached = read_cache_entry("some-fingerprint", 3600)?.expect("just-written entry should hit");
+        assert_eq!(cached.0, IndicatedUpdateRequirement::UpdateRequired);
+        assert_eq!(cached.1.projects[0].target_frameworks[0].dependencies[0].name, "Some.Package");
+
+        assert!(read_cache_entry("other-fingerprint", 3600)?.is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_cache_entry_stale_expiry_miss() -> Result<(), Error> {
+        let dir = tempfile::tempdir()?;
+        let _guard = CacheDirEnvGuard::set(dir.path());
+
+        let data = test_data(vec![test_dependency("Some.Package", None)]);
+        let path = cache_entry_path("some-fingerprint")?;
+        std::fs::create_dir_all(path.parent().expect("cache path always has a parent"))?;
+        let stale_entry = CacheEntryRef {
+            cached_at: std::time::SystemTime::now() - std::time::Duration::from_secs(120),
+            update_requirement: &IndicatedUpdateRequirement::UpdateRequired,
+            data: &data,
+        };
+        std::fs::write(&path, serde_json::to_string(&stale_entry)?)?;
+
+        assert!(read_cache_entry("some-fingerprint", 60)?.is_none());
+        assert!(read_cache_entry("some-fingerprint", 3600)?.is_some());
+        Ok(())
+    }
+
+    #[test]
+    fn test_clear_cache_removes_cached_entries() -> Result<(), Error> {
+        let dir = tempfile::tempdir()?;
+        let _guard = CacheDirEnvGuard::set(dir.path());
+
+        let data = test_data(vec![test_dependency("Some.Package", None)]);
+        write_cache_entry("some-fingerprint", &IndicatedUpdateRequirement::UpdateRequired, &data)?;
+        assert!(read_cache_entry("some-fingerprint", 3600)?.is_some());
+
+        clear_cache()?;
+        assert!(read_cache_entry("some-fingerprint", 3600)?.is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn test_native_inventory() -> Result<(), Error> {
+        let dir = tempfile::tempdir()?;
+        std::fs::write(
+            dir.path().join("Example.csproj"),
+            r#"<Project Sdk="Microsoft.NET.Sdk">
+  <PropertyGroup>
+    <TargetFrameworks>net6.0;net7.0</TargetFrameworks>
+  </PropertyGroup>
+  <ItemGroup>
+    <PackageReference Include="Newtonsoft.Json" Version="13.0.1" />
+  </ItemGroup>
+</Project>
+"#,
+        )?;
+
+        let data = native_inventory(dir.path())?;
+        assert_eq!(data.projects.len(), 1);
+        assert_eq!(data.projects[0].target_frameworks.len(), 2);
+        assert_eq!(
+            data.projects[0].target_frameworks[0].dependencies[0].name,
+            "Newtonsoft.Json"
+        );
+        assert_eq!(
+            data.projects[0].target_frameworks[0].dependencies[0].resolved_version,
+            "13.0.1"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_native_inventory_does_not_use_global_json_msbuild_sdks_as_package_versions() -> Result<(), Error> {
+        // `msbuild-sdks` pins MSBuild project SDK versions, not
+        // `<PackageReference>` versions, so a name collision with one must
+        // not resolve a versionless reference (unlike central package
+        // management, see `test_native_inventory_directory_packages_props`).
+        let dir = tempfile::tempdir()?;
+        std::fs::write(
+            dir.path().join("global.json"),
+            r#"{
+  "sdk": { "version": "7.0.100" },
+  "msbuild-sdks": { "Same.Name": "9.9.9" }
+}
+"#,
+        )?;
+        std::fs::write(
+            dir.path().join("Example.csproj"),
+            r#"<Project Sdk="Microsoft.NET.Sdk">
+  <PropertyGroup>
+    <TargetFramework>net6.0</TargetFramework>
+  </PropertyGroup>
+  <ItemGroup>
+    <PackageReference Include="Same.Name" />
+  </ItemGroup>
+</Project>
+"#,
+        )?;
+
+        let data = native_inventory(dir.path())?;
+        assert_eq!(
+            data.projects[0].target_frameworks[0].dependencies[0].resolved_version,
+            ""
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_native_inventory_directory_packages_props() -> Result<(), Error> {
+        let dir = tempfile::tempdir()?;
+        std::fs::write(
+            dir.path().join("Directory.Packages.props"),
+            r#"<Project>
+  <ItemGroup>
+    <PackageVersion Include="Centrally.Pinned.Package" Version="9.9.9" />
+  </ItemGroup>
+</Project>
+"#,
+        )?;
+        std::fs::write(
+            dir.path().join("Example.csproj"),
+            r#"<Project Sdk="Microsoft.NET.Sdk">
+  <PropertyGroup>
+    <TargetFramework>net6.0</TargetFramework>
+  </PropertyGroup>
+  <ItemGroup>
+    <PackageReference Include="Centrally.Pinned.Package" />
+  </ItemGroup>
+</Project>
+"#,
+        )?;
+
+        let data = native_inventory(dir.path())?;
+        assert_eq!(
+            data.projects[0].target_frameworks[0].dependencies[0].resolved_version,
+            "9.9.9"
+        );
+        Ok(())
+    }
 }